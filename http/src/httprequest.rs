@@ -1,6 +1,8 @@
 use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Method {
     Get,
     Post,
@@ -96,87 +98,137 @@ pub struct HttpRequest {
     pub version: Version,
     pub resource: Resource,
     pub headers: HashMap<String, String>,
-    pub msg_body: String,
+    pub body: Vec<u8>,
+    /// Path parameters captured by the `Router` from a pattern like `/api/shipping/orders/:id`.
+    pub params: HashMap<String, String>,
 }
 
-/// Converts a `String` into an `HttpRequest` struct.
+impl HttpRequest {
+    /// Returns the request body decoded as a UTF-8 string, substituting the
+    /// replacement character for any bytes that aren't valid UTF-8.
+    ///
+    /// # Returns
+    ///
+    /// A `String` containing the request body.
+    pub fn msg_body(&self) -> String {
+        String::from_utf8_lossy(&self.body).to_string()
+    }
+}
+
+/// The ways parsing the header section of an HTTP request can fail.
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    /// The request line was missing or didn't have a method, resource, and version.
+    IncompleteRequestLine,
+    /// The header section contained bytes that aren't valid UTF-8.
+    InvalidUtf8,
+    /// A header line had no colon separating its name from its value.
+    MalformedHeader(String),
+    /// The request line named a method other than `GET` or `POST`.
+    UnsupportedMethod(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::IncompleteRequestLine => {
+                write!(f, "request line is missing or incomplete")
+            }
+            ParseError::InvalidUtf8 => write!(f, "header section is not valid UTF-8"),
+            ParseError::MalformedHeader(line) => write!(f, "malformed header line: {}", line),
+            ParseError::UnsupportedMethod(method) => write!(f, "unsupported method: {}", method),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses the header section of an HTTP request (request line plus headers, with no trailing
+/// blank line or body) into an `HttpRequest`.
 ///
-/// This implementation parses the provided `String` line by line to extract the HTTP method,
-/// resource, version, headers, and message body. It then constructs and returns an `HttpRequest`
-/// struct with the parsed values.
+/// This implementation parses the header bytes line by line to extract the HTTP method,
+/// resource, version, and headers. The returned request's `body` is always empty; callers
+/// that have already read a `Content-Length` worth of body bytes should assign them
+/// afterwards.
 ///
 /// # Arguments
 ///
-/// * `req` - The `String` representation of the HTTP request.
+/// * `buf` - The bytes of the header section of an HTTP request.
 ///
 /// # Returns
 ///
-/// An `HttpRequest` struct with the parsed values.
-impl From<String> for HttpRequest {
-    fn from(req: String) -> Self {
-        let mut parsed_method = Method::Uninitialized;
+/// An `HttpRequest` with the parsed values, or a `ParseError` describing what was wrong.
+impl TryFrom<&[u8]> for HttpRequest {
+    type Error = ParseError;
+
+    fn try_from(buf: &[u8]) -> Result<Self, Self::Error> {
+        let headers_section = std::str::from_utf8(buf).map_err(|_| ParseError::InvalidUtf8)?;
+
+        let mut parsed_method = None;
         let mut parsed_version = Version::V1_1;
         let mut parsed_resource = Resource::Path("".to_string());
         let mut parsed_headers = HashMap::new();
-        let mut parsed_msg_body = "";
 
-        for line in req.lines() {
-            if line.contains("HTTP") {
-                let (method, resource, version) = parse_request_line(line);
-                parsed_method = method;
+        let mut request_line_parsed = false;
+        for line in headers_section.lines() {
+            if line.is_empty() {
+                continue;
+            } else if !request_line_parsed {
+                let (method, resource, version) = parse_request_line(line)?;
+                parsed_method = Some(method);
                 parsed_resource = resource;
                 parsed_version = version;
-            } else if line.contains(":") {
-                let (key, value) = parse_header_line(line);
-                parsed_headers.insert(key, value);
-            } else if line.len() == 0 {
-                // Empty line indicates the end of the headers
-                // Empty instructions, delivered to the operating system, are ignored.
+                request_line_parsed = true;
             } else {
-                parsed_msg_body = line;
+                let (key, value) = parse_header_line(line)?;
+                parsed_headers.insert(key, value);
             }
         }
 
-        HttpRequest {
-            method: parsed_method,
+        Ok(HttpRequest {
+            method: parsed_method.ok_or(ParseError::IncompleteRequestLine)?,
             version: parsed_version,
             resource: parsed_resource,
             headers: parsed_headers,
-            msg_body: parsed_msg_body.to_string(),
-        }
+            body: Vec::new(),
+            params: HashMap::new(),
+        })
     }
 }
 
-fn parse_request_line(s: &str) -> (Method, Resource, Version) {
+fn parse_request_line(s: &str) -> Result<(Method, Resource, Version), ParseError> {
     // An iterator over the whitespace-separated words in the input string.
     // Example："GET /index.html HTTP/1.1" to be ["GET", "/index.html", "HTTP/1.1"]
     let mut words = s.split_whitespace();
-    let method = words.next().unwrap();
-    let resource = words.next().unwrap();
-    let version = words.next().unwrap();
+    let method = words.next().ok_or(ParseError::IncompleteRequestLine)?;
+    let resource = words.next().ok_or(ParseError::IncompleteRequestLine)?;
+    let version = words.next().ok_or(ParseError::IncompleteRequestLine)?;
+
+    let parsed_method: Method = method.into();
+    if parsed_method == Method::Uninitialized {
+        return Err(ParseError::UnsupportedMethod(method.to_string()));
+    }
 
-    (
-        method.into(),
+    Ok((
+        parsed_method,
         Resource::Path(resource.to_string()),
         version.into(),
-    )
+    ))
 }
 
-fn parse_header_line(s: &str) -> (String, String) {
+fn parse_header_line(s: &str) -> Result<(String, String), ParseError> {
     // Represents the items in the header of an HTTP request.
     // Example: "Host: localhost:3000" to be ["Host", "localhost:3000"]
-    // to slice in ":"
-    let mut header_items = s.split(":");
-    let mut key = String::from("");
-    let mut value = String::from("");
-    if let Some(k) = header_items.next() {
-        key = k.to_string();
-    }
-    if let Some(v) = header_items.next() {
-        value = v.to_string();
-    }
+    // Split only on the first colon so values containing one (e.g. "localhost:3000") survive.
+    let mut header_items = s.splitn(2, ':');
+    let key = header_items
+        .next()
+        .ok_or_else(|| ParseError::MalformedHeader(s.to_string()))?;
+    let value = header_items
+        .next()
+        .ok_or_else(|| ParseError::MalformedHeader(s.to_string()))?;
 
-    (key, value)
+    Ok((key.trim().to_string(), value.trim().to_string()))
 }
 
 #[cfg(test)]
@@ -197,16 +249,47 @@ mod tests {
 
     #[test]
     fn test_read_http() {
-        let s: String = String::from("GET /greeting HTTP/1.1\r\nHost: localhost:3000\r\nUser-Agent: curl/7.71.1\r\nAccept: */*\r\n\r\n");
+        let s = "GET /greeting HTTP/1.1\r\nHost: localhost:3000\r\nUser-Agent: curl/7.71.1\r\nAccept: */*";
         let mut headers_expected = HashMap::new();
-        headers_expected.insert("Host".into(), " localhost".into());
-        headers_expected.insert("Accept".into(), " */*".into());
-        headers_expected.insert("User-Agent".into(), " curl/7.71.1".into());
-        let req: HttpRequest = s.into();
+        headers_expected.insert("Host".into(), "localhost:3000".into());
+        headers_expected.insert("Accept".into(), "*/*".into());
+        headers_expected.insert("User-Agent".into(), "curl/7.71.1".into());
+        let req = HttpRequest::try_from(s.as_bytes()).unwrap();
 
         assert_eq!(Method::Get, req.method);
         assert_eq!(Version::V1_1, req.version);
         assert_eq!(Resource::Path("/greeting".to_string()), req.resource);
         assert_eq!(headers_expected, req.headers);
     }
+
+    #[test]
+    fn test_incomplete_request_line_is_rejected() {
+        let s = "GET /greeting\r\nHost: localhost:3000";
+        let err = HttpRequest::try_from(s.as_bytes()).unwrap_err();
+        assert_eq!(ParseError::IncompleteRequestLine, err);
+    }
+
+    #[test]
+    fn test_malformed_header_is_rejected() {
+        let s = "GET /greeting HTTP/1.1\r\nHost localhost 3000";
+        let err = HttpRequest::try_from(s.as_bytes()).unwrap_err();
+        assert_eq!(
+            ParseError::MalformedHeader("Host localhost 3000".to_string()),
+            err
+        );
+    }
+
+    #[test]
+    fn test_unsupported_method_is_rejected() {
+        let s = "PUT /greeting HTTP/1.1\r\nHost: localhost:3000";
+        let err = HttpRequest::try_from(s.as_bytes()).unwrap_err();
+        assert_eq!(ParseError::UnsupportedMethod("PUT".to_string()), err);
+    }
+
+    #[test]
+    fn test_invalid_utf8_is_rejected() {
+        let bytes = [b'G', b'E', b'T', b' ', 0xff, 0xfe];
+        let err = HttpRequest::try_from(&bytes[..]).unwrap_err();
+        assert_eq!(ParseError::InvalidUtf8, err);
+    }
 }
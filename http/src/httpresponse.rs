@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::io::{Result, Write};
+
+/// An outgoing HTTP response: status line, headers, and an optional byte body.
+///
+/// Headers passed to `new` are copied into an owned map so a response can be built from
+/// borrowed data and still have headers added afterwards (e.g. to echo `Connection` back to the
+/// client, or by a middleware that injects common headers). The body is raw bytes rather than a
+/// `String` so binary assets (images, fonts, `.wasm`) round-trip intact.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpResponse {
+    version: String,
+    status_code: String,
+    status_text: String,
+    headers: HashMap<String, String>,
+    body: Option<Vec<u8>>,
+}
+
+impl HttpResponse {
+    /// Builds a response with the given status code, headers, and body.
+    ///
+    /// # Arguments
+    ///
+    /// * `status_code` - The HTTP status code, e.g. `"200"` or `"404"`.
+    /// * `headers` - Extra response headers. `Content-Type` defaults to `text/html` if not set.
+    /// * `body` - The response body, if any.
+    ///
+    /// # Returns
+    ///
+    /// The constructed `HttpResponse`.
+    pub fn new(
+        status_code: &str,
+        headers: Option<HashMap<&str, &str>>,
+        body: Option<Vec<u8>>,
+    ) -> HttpResponse {
+        let mut owned_headers: HashMap<String, String> = headers
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        owned_headers
+            .entry("Content-Type".to_string())
+            .or_insert_with(|| "text/html".to_string());
+
+        HttpResponse {
+            version: "HTTP/1.1".to_string(),
+            status_text: status_text_for(status_code).to_string(),
+            status_code: status_code.to_string(),
+            headers: owned_headers,
+            body,
+        }
+    }
+
+    /// Inserts or overwrites a response header.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The header name.
+    /// * `value` - The header value.
+    pub fn set_header(&mut self, key: &str, value: &str) {
+        self.headers.insert(key.to_string(), value.to_string());
+    }
+
+    /// Returns the response's status code, e.g. `"200"`.
+    pub fn status_code(&self) -> &str {
+        &self.status_code
+    }
+
+    /// Writes the response, including the `Content-Length` header it computes from the body, to
+    /// `stream`.
+    ///
+    /// # Arguments
+    ///
+    /// * `stream` - The destination to write the response to.
+    pub fn send_response(&self, stream: &mut impl Write) -> Result<()> {
+        let body = self.body.as_deref().unwrap_or(&[]);
+
+        let mut header_lines = String::new();
+        for (key, value) in &self.headers {
+            header_lines.push_str(&format!("{}: {}\r\n", key, value));
+        }
+
+        write!(
+            stream,
+            "{} {} {}\r\n{}Content-Length: {}\r\n\r\n",
+            self.version,
+            self.status_code,
+            self.status_text,
+            header_lines,
+            body.len(),
+        )?;
+        stream.write_all(body)
+    }
+}
+
+fn status_text_for(status_code: &str) -> &'static str {
+    match status_code {
+        "200" => "OK",
+        "201" => "Created",
+        "400" => "Bad Request",
+        "404" => "Not Found",
+        "408" => "Request Timeout",
+        "500" => "Internal Server Error",
+        _ => "Not Found",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_response_struct_creation_200() {
+        let response_actual = HttpResponse::new("200", None, Some(b"xxxx".to_vec()));
+        assert_eq!(response_actual.status_code(), "200");
+        assert_eq!(response_actual.body, Some(b"xxxx".to_vec()));
+    }
+
+    #[test]
+    fn test_response_struct_creation_404() {
+        let response_actual = HttpResponse::new("404", None, Some(b"xxxx".to_vec()));
+        assert_eq!(response_actual.status_code(), "404");
+        assert_eq!(response_actual.body, Some(b"xxxx".to_vec()));
+    }
+
+    #[test]
+    fn test_set_header_overrides_previous_value() {
+        let mut headers = HashMap::new();
+        headers.insert("Connection", "close");
+        let mut response = HttpResponse::new("200", Some(headers), None);
+
+        response.set_header("Connection", "keep-alive");
+
+        assert_eq!(
+            response.headers.get("Connection").map(String::as_str),
+            Some("keep-alive")
+        );
+    }
+
+    #[test]
+    fn test_http_response_creation() {
+        let response_expected = HttpResponse {
+            version: "HTTP/1.1".into(),
+            status_code: "404".into(),
+            status_text: "Not Found".into(),
+            headers: {
+                let mut h = HashMap::new();
+                h.insert("Content-Type".to_string(), "text/html".to_string());
+                h
+            },
+            body: Some(b"xxxx".to_vec()),
+        };
+        let response_actual = HttpResponse::new("404", None, Some(b"xxxx".to_vec()));
+        assert_eq!(response_actual, response_expected);
+    }
+}
@@ -1,8 +1,14 @@
-use http::{httprequest::HttpRequest, httpresponse::HttpResponse};
+use super::mime;
+use http::{
+    httprequest::{HttpRequest, Method},
+    httpresponse::HttpResponse,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::env;
 use std::fs;
+use std::io;
+use std::sync::{Mutex, OnceLock};
 
 /// A trait representing a handler for HTTP requests.
 pub trait Handler {
@@ -17,32 +23,37 @@ pub trait Handler {
     /// An `HttpResponse` representing the response to the request.
     fn handle(req: &HttpRequest) -> HttpResponse;
 
-    /// Loads the contents of a file.
+    /// Loads the raw bytes of a file under `PUBLIC_PATH`.
     ///
     /// # Arguments
     ///
-    /// * `file_name` - The name of the file to load.
+    /// * `file_name` - The name of the file to load, relative to `PUBLIC_PATH`. Rejected (with
+    ///   `None`) if it contains `..`, so a request can't escape the public directory.
     ///
     /// # Returns
     ///
-    /// An `Option<String>` containing the contents of the file if it exists, or `None` otherwise.
+    /// A `Vec<u8>` of the file's contents if it exists and is within `PUBLIC_PATH`, or `None`
+    /// otherwise.
     ///
     /// # Examples
     ///
     /// ```
     /// let file_contents = Handler::load_file("example.txt");
     /// match file_contents {
-    ///     Some(contents) => println!("File contents: {}", contents),
+    ///     Some(contents) => println!("File is {} bytes", contents.len()),
     ///     None => println!("File not found"),
     /// }
     /// ```
-    fn load_file(file_name: &str) -> Option<String> {
+    fn load_file(file_name: &str) -> Option<Vec<u8>> {
+        if file_name.contains("..") {
+            return None;
+        }
+
         let default_path = format!("{}/public", env!("CARGO_MANIFEST_DIR"));
         let public_path = env::var("PUBLIC_PATH").unwrap_or(default_path);
         let full_path = format!("{}/{}", public_path, file_name);
 
-        let contents = fs::read_to_string(full_path.clone());
-        contents.ok()
+        fs::read(full_path).ok()
     }
 }
 pub struct StaticPageHandler;
@@ -94,17 +105,9 @@ impl Handler for StaticPageHandler {
             "health" => HttpResponse::new("200", None, Self::load_file("health.html")),
             path => match Self::load_file(path) {
                 Some(contents) => {
-                    println!("Serving file: {} with contents:\n{}", path, contents);
+                    println!("Serving file: {} ({} bytes)", path, contents.len());
                     let mut map: HashMap<&str, &str> = HashMap::new();
-
-                    // Set the appropriate Content-Type header based on the file extension
-                    if path.ends_with(".css") {
-                        map.insert("Content-Type", "text/css");
-                    } else if path.ends_with(".js") {
-                        map.insert("Content-Type", "application/javascript");
-                    } else {
-                        map.insert("Content-Type", "text/html");
-                    }
+                    map.insert("Content-Type", mime::content_type_for(path));
 
                     HttpResponse::new("200", Some(map), Some(contents))
                 }
@@ -115,14 +118,114 @@ impl Handler for StaticPageHandler {
 }
 
 impl WebServiceHandler {
-    fn load_json() -> Vec<OrderStatus> {
+    /// Looks up a single order by `order_id` and responds with it as JSON, or `404` when no
+    /// order matches `id` or `id` isn't a valid order id.
+    fn handle_single_order(id: &str) -> HttpResponse {
+        let orders = match Self::load_json() {
+            Ok(orders) => orders,
+            Err(err) => {
+                eprintln!("Failed to load orders.json: {}", err);
+                return HttpResponse::new("500", None, None);
+            }
+        };
+        let order = id
+            .parse::<i32>()
+            .ok()
+            .and_then(|order_id| orders.into_iter().find(|o| o.order_id == order_id));
+
+        match order {
+            Some(order) => {
+                let body = Some(serde_json::to_string(&order).unwrap().into_bytes());
+                let mut headers: HashMap<&str, &str> = HashMap::new();
+                headers.insert("Content-Type", "application/json");
+                HttpResponse::new("200", Some(headers), body)
+            }
+            None => HttpResponse::new("404", None, Self::load_file("404.html")),
+        }
+    }
+
+    /// Reads and parses `orders.json`, without panicking on a transient read failure or
+    /// corrupted contents — this runs inside `handle_create_order`'s critical section, and a
+    /// panic there would poison `orders_lock` and take down every later request to this
+    /// endpoint.
+    fn load_json() -> io::Result<Vec<OrderStatus>> {
+        let json_contents = fs::read_to_string(Self::data_file_path())?;
+        serde_json::from_str(&json_contents)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Serializes access to `orders.json`'s read-modify-write cycle, so two requests handled by
+    /// different `ThreadPool` workers can't both load the same old contents and clobber each
+    /// other's write with the last rename to land.
+    fn orders_lock() -> &'static Mutex<()> {
+        static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+        LOCK.get_or_init(|| Mutex::new(()))
+    }
+
+    /// The path to `orders.json` under `DATA_PATH` (or the crate's `data` directory by default).
+    fn data_file_path() -> String {
         let default_path = format!("{}/data", env!("CARGO_MANIFEST_DIR"));
         let data_path = env::var("DATA_PATH").unwrap_or(default_path);
-        let full_path = format!("{}/{}", data_path, "orders.json");
-        let json_contents = fs::read_to_string(full_path);
-        let orders: Vec<OrderStatus> =
-            serde_json::from_str(json_contents.unwrap().as_str()).unwrap();
-        orders
+        format!("{}/{}", data_path, "orders.json")
+    }
+
+    /// Writes `orders` to `orders.json` atomically: the new contents are written to a temp file
+    /// next to it, then moved into place with a rename, so a crash mid-write can't leave the file
+    /// truncated or half-written.
+    fn save_json(orders: &[OrderStatus]) -> io::Result<()> {
+        let path = Self::data_file_path();
+        let temp_path = format!("{}.tmp", path);
+        fs::write(&temp_path, serde_json::to_string(orders).unwrap())?;
+        fs::rename(&temp_path, &path)
+    }
+
+    /// Deserializes `req`'s body into an `OrderStatus`, appends it to the persisted orders, and
+    /// responds with the stored record.
+    ///
+    /// # Returns
+    ///
+    /// `201 Created` with the stored order as JSON, `400 Bad Request` if the body isn't a
+    /// valid `OrderStatus`, or `500 Internal Server Error` if `orders.json` can't be read or
+    /// written.
+    fn handle_create_order(req: &HttpRequest) -> HttpResponse {
+        let order: OrderStatus = match serde_json::from_slice(&req.body) {
+            Ok(order) => order,
+            Err(err) => {
+                let body = Some(
+                    serde_json::json!({ "error": err.to_string() })
+                        .to_string()
+                        .into_bytes(),
+                );
+                let mut headers: HashMap<&str, &str> = HashMap::new();
+                headers.insert("Content-Type", "application/json");
+                return HttpResponse::new("400", Some(headers), body);
+            }
+        };
+
+        let _guard = Self::orders_lock()
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        let mut orders = match Self::load_json() {
+            Ok(orders) => orders,
+            Err(err) => {
+                eprintln!("Failed to load orders.json: {}", err);
+                return HttpResponse::new("500", None, None);
+            }
+        };
+        orders.push(order);
+        if let Err(err) = Self::save_json(&orders) {
+            eprintln!("Failed to persist orders.json: {}", err);
+            return HttpResponse::new("500", None, None);
+        }
+
+        let body = Some(
+            serde_json::to_string(orders.last().unwrap())
+                .unwrap()
+                .into_bytes(),
+        );
+        let mut headers: HashMap<&str, &str> = HashMap::new();
+        headers.insert("Content-Type", "application/json");
+        HttpResponse::new("201", Some(headers), body)
     }
 }
 
@@ -138,23 +241,25 @@ impl Handler for WebServiceHandler {
     ///
     /// An `HttpResponse` object representing the response to the request.
     fn handle(req: &HttpRequest) -> HttpResponse {
-        // Extract the path from the request resource
-        let http::httprequest::Resource::Path(s) = &req.resource;
-        let route: Vec<&str> = s.split('/').collect();
+        if req.method == Method::Post {
+            return Self::handle_create_order(req);
+        }
 
-        // Check the route and generate the appropriate response
-        match route[2] {
-            "shipping" if route.len() > 2 && route[3] == "orders" => {
-                // Generate a JSON response with a 200 status code
-                let body = Some(serde_json::to_string(&Self::load_json()).unwrap());
-                let mut headers: HashMap<&str, &str> = HashMap::new();
-                headers.insert("Content-Type", "application/json");
-                HttpResponse::new("200", Some(headers), body)
-            }
-            _ => {
-                // Generate a 404 response with a custom HTML file
-                HttpResponse::new("404", None, Self::load_file("404.html"))
-            }
+        // The router captures `:id` from `/api/shipping/orders/:id` into `req.params`.
+        match req.params.get("id") {
+            Some(id) => Self::handle_single_order(id),
+            None => match Self::load_json() {
+                Ok(orders) => {
+                    let body = Some(serde_json::to_string(&orders).unwrap().into_bytes());
+                    let mut headers: HashMap<&str, &str> = HashMap::new();
+                    headers.insert("Content-Type", "application/json");
+                    HttpResponse::new("200", Some(headers), body)
+                }
+                Err(err) => {
+                    eprintln!("Failed to load orders.json: {}", err);
+                    HttpResponse::new("500", None, None)
+                }
+            },
         }
     }
 }
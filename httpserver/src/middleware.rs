@@ -0,0 +1,208 @@
+use http::{httprequest, httprequest::HttpRequest, httpresponse::HttpResponse};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// A link in the middleware chain that wraps `Router`'s handler dispatch.
+///
+/// A middleware can inspect or modify the request before calling `next` to continue the chain,
+/// and can inspect or modify the `HttpResponse` `next` returns — including short-circuiting the
+/// chain entirely by returning a response without calling `next` at all.
+///
+/// `Send + Sync` so a `Router` (and its `Vec<Box<dyn Middleware>>`) can be shared across the
+/// `ThreadPool`'s worker threads via `Arc`.
+pub trait Middleware: Send + Sync {
+    fn handle(
+        &self,
+        req: &HttpRequest,
+        next: &mut dyn FnMut(&HttpRequest) -> HttpResponse,
+    ) -> HttpResponse;
+}
+
+/// Logs the method, path, status code, and elapsed time of every request.
+pub struct RequestLogger;
+
+impl Middleware for RequestLogger {
+    fn handle(
+        &self,
+        req: &HttpRequest,
+        next: &mut dyn FnMut(&HttpRequest) -> HttpResponse,
+    ) -> HttpResponse {
+        let httprequest::Resource::Path(path) = &req.resource;
+        let path = path.clone();
+        let method = format!("{:?}", req.method);
+        let start = Instant::now();
+
+        let resp = next(req);
+
+        println!(
+            "{} {} {} {:?}",
+            method,
+            path,
+            resp.status_code(),
+            start.elapsed()
+        );
+        resp
+    }
+}
+
+/// Injects the `Server` and `Date` headers into every response.
+pub struct CommonHeaders;
+
+impl Middleware for CommonHeaders {
+    fn handle(
+        &self,
+        req: &HttpRequest,
+        next: &mut dyn FnMut(&HttpRequest) -> HttpResponse,
+    ) -> HttpResponse {
+        let mut resp = next(req);
+        resp.set_header("Server", "rust_webapp");
+        resp.set_header("Date", &http_date_now());
+        resp
+    }
+}
+
+const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Formats the current time as an RFC 1123 date, e.g. `Tue, 15 Nov 1994 08:12:31 GMT`.
+fn http_date_now() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let days = (secs / 86_400) as i64;
+    let time_of_day = secs % 86_400;
+    let (hour, minute, second) = (
+        time_of_day / 3600,
+        (time_of_day % 3600) / 60,
+        time_of_day % 60,
+    );
+    let (year, month, day) = civil_from_days(days);
+    // 1970-01-01 (day 0) was a Thursday.
+    let weekday = WEEKDAYS[days.rem_euclid(7) as usize];
+
+    format!(
+        "{}, {:02} {} {} {:02}:{:02}:{:02} GMT",
+        weekday,
+        day,
+        MONTHS[(month - 1) as usize],
+        year,
+        hour,
+        minute,
+        second
+    )
+}
+
+/// Converts a day count since the Unix epoch into a (year, month, day) civil date, using Howard
+/// Hinnant's `civil_from_days` algorithm.
+fn civil_from_days(z: i64) -> (i64, i64, i64) {
+    let z = z + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = (z - era * 146_097) as u64;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let y = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as i64;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as i64;
+    let year = if month <= 2 { y + 1 } else { y };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::httprequest::{Method, Resource, Version};
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    fn request() -> HttpRequest {
+        HttpRequest {
+            method: Method::Get,
+            version: Version::V1_1,
+            resource: Resource::Path("/".to_string()),
+            headers: HashMap::new(),
+            body: Vec::new(),
+            params: HashMap::new(),
+        }
+    }
+
+    /// Records `label` before calling `next`, mirroring how `Router::route` wraps dispatch.
+    struct Recorder {
+        label: &'static str,
+        calls: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    impl Middleware for Recorder {
+        fn handle(
+            &self,
+            req: &HttpRequest,
+            next: &mut dyn FnMut(&HttpRequest) -> HttpResponse,
+        ) -> HttpResponse {
+            self.calls.lock().unwrap().push(self.label);
+            next(req)
+        }
+    }
+
+    /// A middleware that never calls `next`, short-circuiting the chain.
+    struct ShortCircuit;
+
+    impl Middleware for ShortCircuit {
+        fn handle(
+            &self,
+            _req: &HttpRequest,
+            _next: &mut dyn FnMut(&HttpRequest) -> HttpResponse,
+        ) -> HttpResponse {
+            HttpResponse::new("503", None, None)
+        }
+    }
+
+    /// Wraps a terminal `200` dispatch in `middlewares`, outermost first — the same fold
+    /// `Router::route` uses.
+    fn run_chain(middlewares: &[Box<dyn Middleware>], req: &HttpRequest) -> HttpResponse {
+        let mut chain: Box<dyn FnMut(&HttpRequest) -> HttpResponse> =
+            Box::new(|_req: &HttpRequest| HttpResponse::new("200", None, None));
+        for middleware in middlewares.iter().rev() {
+            let mut inner = chain;
+            chain = Box::new(move |req: &HttpRequest| middleware.handle(req, &mut *inner));
+        }
+        chain(req)
+    }
+
+    #[test]
+    fn test_middlewares_run_in_registration_order() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let middlewares: Vec<Box<dyn Middleware>> = vec![
+            Box::new(Recorder {
+                label: "first",
+                calls: Arc::clone(&calls),
+            }),
+            Box::new(Recorder {
+                label: "second",
+                calls: Arc::clone(&calls),
+            }),
+        ];
+
+        run_chain(&middlewares, &request());
+
+        assert_eq!(*calls.lock().unwrap(), vec!["first", "second"]);
+    }
+
+    #[test]
+    fn test_middleware_can_short_circuit_without_calling_next() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let middlewares: Vec<Box<dyn Middleware>> = vec![
+            Box::new(ShortCircuit),
+            Box::new(Recorder {
+                label: "never",
+                calls: Arc::clone(&calls),
+            }),
+        ];
+
+        let resp = run_chain(&middlewares, &request());
+
+        assert_eq!(resp.status_code(), "503");
+        assert!(calls.lock().unwrap().is_empty());
+    }
+}
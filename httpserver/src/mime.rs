@@ -0,0 +1,50 @@
+/// Resolves a file name's extension to a MIME type for the `Content-Type` header, defaulting to
+/// `application/octet-stream` for unrecognized or missing extensions.
+///
+/// # Arguments
+///
+/// * `file_name` - The file name (or path) whose extension should be resolved.
+pub fn content_type_for(file_name: &str) -> &'static str {
+    let extension = file_name.rsplit('.').next().unwrap_or("").to_lowercase();
+
+    match extension.as_str() {
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "js" | "mjs" => "application/javascript",
+        "json" | "map" => "application/json",
+        "xml" => "application/xml",
+        "txt" => "text/plain",
+        "csv" => "text/csv",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "ico" => "image/x-icon",
+        "webp" => "image/webp",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+        "pdf" => "application/pdf",
+        "wasm" => "application/wasm",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_extensions() {
+        assert_eq!(content_type_for("style.css"), "text/css");
+        assert_eq!(content_type_for("app.wasm"), "application/wasm");
+        assert_eq!(content_type_for("logo.SVG"), "image/svg+xml");
+    }
+
+    #[test]
+    fn test_unknown_extension_defaults_to_octet_stream() {
+        assert_eq!(content_type_for("data.bin"), "application/octet-stream");
+        assert_eq!(content_type_for("no_extension"), "application/octet-stream");
+    }
+}
@@ -1,48 +1,238 @@
 use super::handler::{Handler, PageNotFoundHandler, StaticPageHandler, WebServiceHandler};
-use http::{httprequest, httprequest::HttpRequest, httpresponse::HttpResponse};
-use std::io::prelude::*;
-
-pub struct Router;
-
-/// Routes the incoming HTTP request to the appropriate handler based on the request method and resource path.
-///
-/// # Arguments
-///
-/// * `req` - The incoming HTTP request.
-/// * `stream` - A mutable reference to the stream to write the response to.
-///
-/// # Examples
-///
-/// ```
-/// use httprequest::HttpRequest;
-/// use std::io::Write;
-///
-/// let req = HttpRequest::new();
-/// let mut stream = Vec::new();
-/// Router::route(req, &mut stream);
-/// ```
-impl Router {
-    pub fn route(req: HttpRequest, stream: &mut impl Write) -> () {
-        match req.method {
-            httprequest::Method::Get => match &req.resource {
-                httprequest::Resource::Path(s) => {
-                    let route: Vec<&str> = s.split("/").collect();
-                    match route[1] {
-                        "api" => {
-                            let resp: HttpResponse = WebServiceHandler::handle(&req);
-                            let _ = resp.send_response(stream);
-                        }
-                        _ => {
-                            let resp: HttpResponse = StaticPageHandler::handle(&req);
-                            let _ = resp.send_response(stream);
-                        }
+use super::middleware::{CommonHeaders, Middleware, RequestLogger};
+use http::{
+    httprequest,
+    httprequest::{HttpRequest, Method},
+    httpresponse::HttpResponse,
+};
+use std::collections::HashMap;
+
+type HandlerFn = fn(&HttpRequest) -> HttpResponse;
+
+/// One segment of a registered route pattern, split on `/`.
+#[derive(Debug)]
+enum Segment {
+    /// A literal segment that must match the request path exactly, e.g. `orders`.
+    Exact(String),
+    /// A segment beginning with `:` that captures the corresponding request segment, e.g. `:id`.
+    Param(String),
+}
+
+fn parse_pattern(pattern: &str) -> Vec<Segment> {
+    pattern
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| match segment.strip_prefix(':') {
+            Some(name) => Segment::Param(name.to_string()),
+            None => Segment::Exact(segment.to_string()),
+        })
+        .collect()
+}
+
+struct Route {
+    method: Method,
+    segments: Vec<Segment>,
+    handler: HandlerFn,
+}
+
+impl Route {
+    /// Tries to match `path_segments` against this route's pattern, returning the number of
+    /// exact-segment matches (used to prefer more specific routes) and the captured params.
+    fn matches(
+        &self,
+        method: Method,
+        path_segments: &[&str],
+    ) -> Option<(usize, HashMap<String, String>)> {
+        if self.method != method || self.segments.len() != path_segments.len() {
+            return None;
+        }
+
+        let mut params = HashMap::new();
+        let mut exact_matches = 0;
+        for (segment, value) in self.segments.iter().zip(path_segments.iter()) {
+            match segment {
+                Segment::Exact(expected) => {
+                    if expected != value {
+                        return None;
                     }
+                    exact_matches += 1;
+                }
+                Segment::Param(name) => {
+                    params.insert(name.clone(), value.to_string());
                 }
-            },
-            _ => {
-                let resp: HttpResponse = PageNotFoundHandler::handle(&req);
-                let _ = resp.send_response(stream);
             }
         }
+
+        Some((exact_matches, params))
+    }
+}
+
+/// A registration-based route table: handlers are registered against path patterns such as
+/// `/api/shipping/orders/:id`, and incoming requests are matched segment by segment, with
+/// segments beginning with `:` captured into the request's `params` map. Dispatch to the matched
+/// handler runs inside an ordered chain of `Middleware`, outermost first.
+pub struct Router {
+    routes: Vec<Route>,
+    middlewares: Vec<Box<dyn Middleware>>,
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Router {
+            routes: Vec::new(),
+            middlewares: Vec::new(),
+        }
+    }
+
+    /// Appends `middleware` to the end of the chain that wraps handler dispatch.
+    ///
+    /// # Arguments
+    ///
+    /// * `middleware` - The middleware to run on every request, in registration order.
+    pub fn use_middleware(&mut self, middleware: Box<dyn Middleware>) {
+        self.middlewares.push(middleware);
+    }
+
+    /// Registers `handler` to serve requests matching `method` and `pattern`.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The HTTP method the route applies to.
+    /// * `pattern` - A `/`-separated path pattern; a segment starting with `:` is a wildcard
+    ///   that captures the matching request segment.
+    /// * `handler` - The handler function to invoke for a matching request.
+    pub fn register(&mut self, method: Method, pattern: &str, handler: HandlerFn) {
+        self.routes.push(Route {
+            method,
+            segments: parse_pattern(pattern),
+            handler,
+        });
+    }
+
+    /// Routes the incoming HTTP request to the best-matching registered handler, falling back to
+    /// `StaticPageHandler` for unmatched `GET` requests and `PageNotFoundHandler` otherwise, and
+    /// returns the resulting response so the caller can send it (and adjust headers, e.g. for
+    /// keep-alive, beforehand).
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - The incoming HTTP request.
+    pub fn route(&self, mut req: HttpRequest) -> HttpResponse {
+        let httprequest::Resource::Path(path) = &req.resource;
+        let path_segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+        let best_match = self
+            .routes
+            .iter()
+            .filter_map(|route| {
+                route
+                    .matches(req.method, &path_segments)
+                    .map(|(exact_matches, params)| (exact_matches, route.handler, params))
+            })
+            .max_by_key(|(exact_matches, ..)| *exact_matches);
+
+        req.params = best_match
+            .as_ref()
+            .map(|(_, _, params)| params.clone())
+            .unwrap_or_default();
+
+        let dispatch = move |req: &HttpRequest| match &best_match {
+            Some((_, handler, _)) => handler(req),
+            None if req.method == Method::Get => StaticPageHandler::handle(req),
+            None => PageNotFoundHandler::handle(req),
+        };
+        let mut chain: Box<dyn FnMut(&HttpRequest) -> HttpResponse> = Box::new(dispatch);
+
+        for middleware in self.middlewares.iter().rev() {
+            let mut inner = chain;
+            chain = Box::new(move |req: &HttpRequest| middleware.handle(req, &mut *inner));
+        }
+
+        chain(&req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::httprequest::{Resource, Version};
+
+    fn request(method: Method, path: &str) -> HttpRequest {
+        HttpRequest {
+            method,
+            version: Version::V1_1,
+            resource: Resource::Path(path.to_string()),
+            headers: HashMap::new(),
+            body: Vec::new(),
+            params: HashMap::new(),
+        }
+    }
+
+    fn exact_handler(_req: &HttpRequest) -> HttpResponse {
+        HttpResponse::new("200", None, Some(b"exact".to_vec()))
+    }
+
+    fn wildcard_handler(_req: &HttpRequest) -> HttpResponse {
+        HttpResponse::new("200", None, Some(b"wildcard".to_vec()))
+    }
+
+    #[test]
+    fn test_exact_route_beats_wildcard_at_same_depth() {
+        let mut router = Router::new();
+        router.register(Method::Get, "/orders/:id", wildcard_handler);
+        router.register(Method::Get, "/orders/count", exact_handler);
+
+        let resp = router.route(request(Method::Get, "/orders/count"));
+
+        assert_eq!(
+            resp,
+            HttpResponse::new("200", None, Some(b"exact".to_vec()))
+        );
+    }
+
+    #[test]
+    fn test_unmatched_get_falls_through_to_static_page_handler() {
+        let router = Router::new();
+
+        let resp = router.route(request(Method::Get, "/health"));
+
+        assert_eq!(resp.status_code(), "200");
+    }
+
+    #[test]
+    fn test_unmatched_non_get_falls_through_to_page_not_found_handler() {
+        let router = Router::new();
+
+        let resp = router.route(request(Method::Post, "/nope"));
+
+        assert_eq!(resp.status_code(), "404");
+    }
+}
+
+impl Default for Router {
+    /// Builds the router used by `Server`, registering the application's API routes and the
+    /// default middleware chain (request logging, then common response headers). Unmatched `GET`
+    /// requests still fall through to `StaticPageHandler` for static assets and the root and
+    /// health-check pages.
+    fn default() -> Self {
+        let mut router = Router::new();
+        router.register(
+            Method::Get,
+            "/api/shipping/orders",
+            WebServiceHandler::handle,
+        );
+        router.register(
+            Method::Get,
+            "/api/shipping/orders/:id",
+            WebServiceHandler::handle,
+        );
+        router.register(
+            Method::Post,
+            "/api/shipping/orders",
+            WebServiceHandler::handle,
+        );
+        router.use_middleware(Box::new(RequestLogger));
+        router.use_middleware(Box::new(CommonHeaders));
+        router
     }
 }
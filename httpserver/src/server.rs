@@ -1,16 +1,32 @@
 use super::router::Router;
-use http::httprequest::HttpRequest;
-use std::io::prelude::*;
-use std::net::TcpListener;
+use super::threadpool::ThreadPool;
+use http::{
+    httprequest::{HttpRequest, Version},
+    httpresponse::HttpResponse,
+};
+use std::convert::TryFrom;
+use std::io::{self, prelude::*};
+use std::net::{TcpListener, TcpStream};
 use std::str;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The default number of worker threads used when `Server::new` doesn't specify one.
+const DEFAULT_THREAD_COUNT: usize = 4;
+
+/// How long a connection may sit idle without delivering a complete request before it is closed
+/// with a `408 Request Timeout`.
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
 
 pub struct Server<'a> {
     socket_addr: &'a str,
+    thread_count: usize,
 }
 
 /// Represents a server that listens for incoming connections and handles HTTP requests.
 impl<'a> Server<'a> {
-    /// Creates a new instance of the server with the specified socket address.
+    /// Creates a new instance of the server with the specified socket address, using
+    /// `DEFAULT_THREAD_COUNT` worker threads to handle connections concurrently.
     ///
     /// # Arguments
     ///
@@ -20,28 +36,217 @@ impl<'a> Server<'a> {
     ///
     /// A new instance of the server.
     pub fn new(socket_addr: &'a str) -> Self {
-        Server { socket_addr }
+        Server {
+            socket_addr,
+            thread_count: DEFAULT_THREAD_COUNT,
+        }
+    }
+
+    /// Creates a new instance of the server with a caller-chosen number of worker threads.
+    ///
+    /// # Arguments
+    ///
+    /// * `socket_addr` - The socket address to bind the server to.
+    /// * `thread_count` - The number of worker threads in the server's `ThreadPool`.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the server.
+    pub fn with_thread_count(socket_addr: &'a str, thread_count: usize) -> Self {
+        Server {
+            socket_addr,
+            thread_count,
+        }
     }
 
     /// Starts the server and listens for incoming connections.
     ///
     /// This method binds the server to the specified socket address and listens for incoming
-    /// connections. For each incoming connection, it reads the request from the stream, converts
-    /// it into an `HttpRequest`, and passes it to the router for further processing.
+    /// connections. Each accepted stream is handed to a worker thread in a `ThreadPool` so a
+    /// single slow client can't block the others. A worker keeps reading and routing requests
+    /// from the same connection for as long as keep-alive stays in effect (see
+    /// `handle_connection`), closing the connection once the client asks for that or it goes
+    /// silent for longer than `READ_TIMEOUT`.
     pub fn run(&self) {
         let connection_listener = TcpListener::bind(self.socket_addr).unwrap();
         println!("Server running at {}", self.socket_addr);
 
+        let pool = ThreadPool::new(self.thread_count);
+        let router = Arc::new(Router::default());
+
         for stream in connection_listener.incoming() {
-            let mut stream = stream.unwrap();
-            println!("Connection established!");
+            let stream = stream.unwrap();
+            let router = Arc::clone(&router);
+            pool.execute(move || {
+                handle_connection(stream, &router);
+            });
+        }
+    }
+}
+
+/// The outcome of trying to read more bytes off a connection.
+enum ReadEvent {
+    /// `n` more bytes were appended to the caller's buffer.
+    Data(usize),
+    /// The client closed the connection (or a non-timeout I/O error occurred).
+    Closed,
+    /// No data arrived within `READ_TIMEOUT`.
+    TimedOut,
+}
+
+fn read_more(stream: &mut TcpStream, read_buffer: &mut [u8]) -> ReadEvent {
+    match stream.read(read_buffer) {
+        Ok(0) => ReadEvent::Closed,
+        Ok(n) => ReadEvent::Data(n),
+        Err(err)
+            if matches!(
+                err.kind(),
+                io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+            ) =>
+        {
+            ReadEvent::TimedOut
+        }
+        Err(_) => ReadEvent::Closed,
+    }
+}
 
+/// Keeps reading and routing requests from `stream` for as long as keep-alive stays in effect,
+/// closing the connection once the client asks for that, goes silent for longer than
+/// `READ_TIMEOUT`, or disconnects.
+fn handle_connection(mut stream: TcpStream, router: &Router) {
+    println!("Connection established!");
+
+    if stream.set_read_timeout(Some(READ_TIMEOUT)).is_err() {
+        return;
+    }
+
+    loop {
+        let mut raw_request = Vec::new();
+        let header_end = loop {
+            let mut read_buffer = [0; 1024];
+            match read_more(&mut stream, &mut read_buffer) {
+                ReadEvent::Data(n) => {
+                    raw_request.extend_from_slice(&read_buffer[..n]);
+                    if let Some(pos) = find_header_terminator(&raw_request) {
+                        break pos;
+                    }
+                }
+                ReadEvent::Closed => return,
+                ReadEvent::TimedOut => {
+                    let mut resp = HttpResponse::new("408", None, None);
+                    resp.set_header("Connection", "close");
+                    let _ = resp.send_response(&mut stream);
+                    return;
+                }
+            }
+        };
+
+        let mut req = match HttpRequest::try_from(&raw_request[..header_end]) {
+            Ok(req) => req,
+            Err(err) => {
+                println!("Rejecting malformed request: {}", err);
+                let mut resp = HttpResponse::new("400", None, Some(err.to_string().into_bytes()));
+                resp.set_header("Connection", "close");
+                let _ = resp.send_response(&mut stream);
+                return;
+            }
+        };
+
+        // Any bytes read past the header terminator are already body bytes.
+        let mut body = raw_request[header_end + 4..].to_vec();
+        let content_length = req
+            .headers
+            .get("Content-Length")
+            .and_then(|value| value.trim().parse::<usize>().ok())
+            .unwrap_or(0);
+
+        while body.len() < content_length {
             let mut read_buffer = [0; 1024];
-            //println!("read_buffer:{:?}", read_buffer);
-            stream.read(&mut read_buffer).unwrap();
+            match read_more(&mut stream, &mut read_buffer) {
+                ReadEvent::Data(n) => body.extend_from_slice(&read_buffer[..n]),
+                ReadEvent::Closed => return,
+                ReadEvent::TimedOut => {
+                    let mut resp = HttpResponse::new("408", None, None);
+                    resp.set_header("Connection", "close");
+                    let _ = resp.send_response(&mut stream);
+                    return;
+                }
+            }
+        }
+        body.truncate(content_length);
+        req.body = body;
+
+        let keep_alive = should_keep_alive(&req);
+        let mut resp = router.route(req);
+        resp.set_header(
+            "Connection",
+            if keep_alive { "keep-alive" } else { "close" },
+        );
+        let _ = resp.send_response(&mut stream);
+
+        if !keep_alive {
+            return;
+        }
+    }
+}
+
+/// Decides whether the connection should stay open for another request, per the `Connection`
+/// header if the client sent one, falling back to the HTTP/1.1 keep-alive-by-default (and
+/// HTTP/1.0 close-by-default) semantics otherwise.
+fn should_keep_alive(req: &HttpRequest) -> bool {
+    match req
+        .headers
+        .get("Connection")
+        .map(|value| value.to_lowercase())
+    {
+        Some(value) if value == "close" => false,
+        Some(value) if value == "keep-alive" => true,
+        _ => req.version == Version::V1_1,
+    }
+}
+
+/// Finds the index of the `\r\n\r\n` sequence that terminates the HTTP header
+/// section, if the buffer contains one yet.
+fn find_header_terminator(buf: &[u8]) -> Option<usize> {
+    buf.windows(4).position(|window| window == b"\r\n\r\n")
+}
 
-            let req: HttpRequest = String::from_utf8(read_buffer.to_vec()).unwrap().into();
-            Router::route(req, &mut stream);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::httprequest::Method;
+
+    #[test]
+    fn test_find_header_terminator_not_found_on_partial_read() {
+        assert_eq!(find_header_terminator(b"GET / HTTP/1.1\r\nHost: x"), None);
+    }
+
+    #[test]
+    fn test_header_terminator_found_once_chunks_assembled() {
+        // Simulates `handle_connection`'s inner read loop: a request whose header section
+        // arrives as three separate TCP reads, checking for the terminator after each one.
+        let chunks: [&[u8]; 3] = [
+            b"GET /greeting HTTP/1.1\r\n",
+            b"Host: localhost:3000\r\n",
+            b"\r\nbody-bytes",
+        ];
+
+        let mut raw_request = Vec::new();
+        let mut header_end = None;
+        for chunk in chunks {
+            raw_request.extend_from_slice(chunk);
+            header_end = find_header_terminator(&raw_request);
+            if header_end.is_some() {
+                break;
+            }
         }
+
+        let header_end =
+            header_end.expect("terminator should be found once all header chunks arrive");
+        let req = HttpRequest::try_from(&raw_request[..header_end]).unwrap();
+        assert_eq!(req.method, Method::Get);
+
+        let body = &raw_request[header_end + 4..];
+        assert_eq!(body, b"body-bytes");
     }
 }